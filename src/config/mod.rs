@@ -0,0 +1,147 @@
+pub mod profile;
+pub mod threshold;
+
+use crate::ui::themes::theme_minimal::{
+    context_window_segment, cost_segment, directory_segment, git_segment, ikuncode_segment,
+    model_segment, output_style_segment, session_segment, usage_segment,
+};
+use profile::{active_profile_name, apply_profile, Profile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SegmentId {
+    Model,
+    Directory,
+    Git,
+    ContextWindow,
+    Cost,
+    Session,
+    OutputStyle,
+    Usage,
+    IkunCode,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum AnsiColor {
+    Color16 { c16: u8 },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColorConfig {
+    pub icon: Option<AnsiColor>,
+    pub text: Option<AnsiColor>,
+    pub background: Option<AnsiColor>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IconConfig {
+    pub plain: String,
+    pub nerd_font: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TextStyleConfig {
+    pub bold: bool,
+    pub italic: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentConfig {
+    pub id: SegmentId,
+    pub enabled: bool,
+    pub icon: IconConfig,
+    pub colors: ColorConfig,
+    pub styles: TextStyleConfig,
+    pub options: HashMap<String, serde_json::Value>,
+}
+
+/// The statusline hook payload Claude Code feeds in on stdin. Segments
+/// that only need their own `options` (most of them) ignore this.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InputData {
+    #[serde(flatten)]
+    pub raw: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub user_token: String,
+    #[serde(default)]
+    pub user_id: String,
+    #[serde(default = "default_segments")]
+    pub segments: Vec<SegmentConfig>,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+}
+
+fn default_segments() -> Vec<SegmentConfig> {
+    vec![
+        model_segment(),
+        directory_segment(),
+        git_segment(),
+        context_window_segment(),
+        cost_segment(),
+        session_segment(),
+        output_style_segment(),
+        usage_segment(),
+        ikuncode_segment(),
+    ]
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    NoHomeDir,
+}
+
+impl Config {
+    fn config_path() -> Option<PathBuf> {
+        let home = dirs::home_dir()?;
+        Some(home.join(".claude").join("ccline").join("config.json"))
+    }
+
+    /// Reads the config file (falling back to the built-in defaults when
+    /// it doesn't exist), then merges the active profile's overrides on
+    /// top of the base `segments`. The active profile is chosen by a
+    /// `--profile <name>` CLI flag, falling back to `CCLINE_PROFILE`.
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = Self::config_path().ok_or(ConfigError::NoHomeDir)?;
+
+        let mut config = if path.exists() {
+            let content = std::fs::read_to_string(&path).map_err(ConfigError::Io)?;
+            serde_json::from_str(&content).map_err(ConfigError::Parse)?
+        } else {
+            Config {
+                segments: default_segments(),
+                ..Config::default()
+            }
+        };
+
+        if let Some(profile_name) = active_profile_name(cli_profile_flag().as_deref()) {
+            if let Some(profile) = config.profiles.get(&profile_name).cloned() {
+                apply_profile(&mut config.segments, &profile);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Looks for `--profile <name>` (or `--profile=<name>`) among the
+/// process's own arguments.
+fn cli_profile_flag() -> Option<String> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix("--profile=") {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
@@ -0,0 +1,111 @@
+use crate::config::{ColorConfig, IconConfig, SegmentConfig, SegmentId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A partial override for a single segment within a named profile. Fields
+/// left `None` fall through to the base segment config from `segments`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SegmentOverride {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub colors: Option<ColorConfig>,
+    #[serde(default)]
+    pub icon: Option<IconConfig>,
+}
+
+/// A named profile: per-segment overrides layered on top of the base
+/// `segments` list at load time, e.g. a "minimal" profile that disables
+/// `cost`/`usage`, or a "debug" profile that turns them back on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub segments: HashMap<SegmentId, SegmentOverride>,
+}
+
+/// Applies `profile`'s overrides to `segments` in place, matching each
+/// override to its segment by `SegmentId`. Ids the profile doesn't mention
+/// are left untouched; ids a profile mentions but the base config doesn't
+/// have are ignored, since a profile may be shared across configs that
+/// don't enable every segment.
+pub fn apply_profile(segments: &mut [SegmentConfig], profile: &Profile) {
+    for segment in segments.iter_mut() {
+        let Some(segment_override) = profile.segments.get(&segment.id) else {
+            continue;
+        };
+
+        if let Some(enabled) = segment_override.enabled {
+            segment.enabled = enabled;
+        }
+        if let Some(colors) = &segment_override.colors {
+            segment.colors = colors.clone();
+        }
+        if let Some(icon) = &segment_override.icon {
+            segment.icon = icon.clone();
+        }
+    }
+}
+
+/// Resolves which profile should be active: an explicit CLI flag wins,
+/// falling back to the `CCLINE_PROFILE` environment variable, then to no
+/// profile (the base config as-is).
+pub fn active_profile_name(cli_flag: Option<&str>) -> Option<String> {
+    cli_flag
+        .map(|s| s.to_string())
+        .or_else(|| std::env::var("CCLINE_PROFILE").ok().filter(|v| !v.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::IconConfig;
+
+    fn segment(id: SegmentId) -> SegmentConfig {
+        SegmentConfig {
+            id,
+            enabled: true,
+            icon: IconConfig::default(),
+            colors: ColorConfig::default(),
+            styles: Default::default(),
+            options: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn override_merges_only_the_fields_it_sets() {
+        let mut segments = vec![segment(SegmentId::Cost)];
+        segments[0].icon.plain = "$".to_string();
+        let mut profile = Profile::default();
+        profile.segments.insert(
+            SegmentId::Cost,
+            SegmentOverride {
+                enabled: Some(false),
+                colors: None,
+                icon: None,
+            },
+        );
+
+        apply_profile(&mut segments, &profile);
+
+        assert!(!segments[0].enabled);
+        assert_eq!(segments[0].icon.plain, "$");
+    }
+
+    #[test]
+    fn unmatched_segment_id_is_left_untouched() {
+        let mut segments = vec![segment(SegmentId::Cost)];
+        let mut profile = Profile::default();
+        profile.segments.insert(
+            SegmentId::Usage,
+            SegmentOverride {
+                enabled: Some(false),
+                colors: None,
+                icon: None,
+            },
+        );
+
+        apply_profile(&mut segments, &profile);
+
+        assert!(segments[0].enabled);
+    }
+}
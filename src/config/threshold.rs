@@ -0,0 +1,74 @@
+use crate::config::{AnsiColor, SegmentConfig};
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// One rule in a segment's `thresholds` option: once a segment's numeric
+/// value reaches `at`, its text should render in `color` instead of the
+/// segment's static color.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Threshold {
+    pub at: f64,
+    pub color: AnsiColor,
+}
+
+/// Picks the color for `value` out of `thresholds`, in any order: the rule
+/// with the highest `at` that `value` still meets or exceeds wins. Returns `None` if `value` doesn't meet any rule (or
+/// the list is empty), in which case the segment's static color applies.
+pub fn resolve_threshold_color(thresholds: &[Threshold], value: f64) -> Option<AnsiColor> {
+    thresholds
+        .iter()
+        .filter(|t| value >= t.at)
+        .max_by(|a, b| a.at.total_cmp(&b.at))
+        .map(|t| t.color.clone())
+}
+
+/// Reads `segment_config`'s `thresholds` option, resolves a color for
+/// `value` against it, and packages that color as `SegmentData::metadata`
+/// under `"text_color"` — the shape every threshold-aware segment needs to
+/// hand back from `collect`. Returns an empty map when there's no
+/// `thresholds` option or none of its rules fire, same as leaving
+/// `metadata` untouched.
+pub fn threshold_color_metadata(
+    segment_config: Option<&SegmentConfig>,
+    value: f64,
+) -> HashMap<String, String> {
+    let thresholds: Vec<Threshold> = segment_config
+        .and_then(|sc| sc.options.get("thresholds"))
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+
+    let mut metadata = HashMap::new();
+    if let Some(color) = resolve_threshold_color(&thresholds, value) {
+        if let Ok(color_json) = serde_json::to_string(&color) {
+            metadata.insert("text_color".to_string(), color_json);
+        }
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highest_met_threshold_wins() {
+        let thresholds = vec![
+            Threshold { at: 0.0, color: AnsiColor::Color16 { c16: 2 } },
+            Threshold { at: 5.0, color: AnsiColor::Color16 { c16: 3 } },
+            Threshold { at: 20.0, color: AnsiColor::Color16 { c16: 1 } },
+        ];
+        let AnsiColor::Color16 { c16 } = resolve_threshold_color(&thresholds, 10.0).unwrap();
+        assert_eq!(c16, 3);
+    }
+
+    #[test]
+    fn empty_thresholds_resolve_to_none() {
+        assert!(resolve_threshold_color(&[], 100.0).is_none());
+    }
+
+    #[test]
+    fn nan_value_does_not_panic_and_meets_nothing() {
+        let thresholds = vec![Threshold { at: 0.0, color: AnsiColor::Color16 { c16: 2 } }];
+        assert!(resolve_threshold_color(&thresholds, f64::NAN).is_none());
+    }
+}
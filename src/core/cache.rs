@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry<V> {
+    value: V,
+    cached_at: DateTime<Utc>,
+}
+
+/// A stale-while-revalidate cache shared by network-backed segments.
+///
+/// A fresh entry never blocks, and neither does a stale one: the stale
+/// value is returned immediately while `fetch` runs on a detached
+/// background thread that persists its result whenever it lands. A
+/// short-lived statusline process that exits right after rendering can
+/// orphan that thread before it finishes — this is accepted on purpose,
+/// since a segment that needs a hard freshness guarantee should use a
+/// short TTL rather than block every render on the network. A cold
+/// (never seen) key always blocks on `fetch`, since there's no stale
+/// value to fall back to.
+pub struct TtlCache<K, V> {
+    cache_path: PathBuf,
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<K, CacheEntry<V>>>>,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Eq + Hash + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+    V: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    pub fn new(cache_path: PathBuf, ttl: Duration) -> Self {
+        let entries = Self::load(&cache_path).unwrap_or_default();
+        Self {
+            cache_path,
+            ttl,
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    fn load(cache_path: &PathBuf) -> Option<HashMap<K, CacheEntry<V>>> {
+        let content = std::fs::read_to_string(cache_path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn persist(cache_path: &PathBuf, entries: &HashMap<K, CacheEntry<V>>) {
+        if let Some(parent) = cache_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(entries) {
+            let _ = std::fs::write(cache_path, json);
+        }
+    }
+
+    fn is_stale(&self, entry: &CacheEntry<V>) -> bool {
+        let elapsed = Utc::now().signed_duration_since(entry.cached_at);
+        elapsed.num_seconds() >= self.ttl.as_secs() as i64
+    }
+
+    /// Looks up `key`, refreshing it via `fetch` as needed.
+    ///
+    /// - Fresh entry: returned as-is, no refresh.
+    /// - Stale entry: the stale value is returned immediately, and `fetch`
+    ///   is kicked off on a detached background thread that persists
+    ///   whatever it finds for next time.
+    /// - No entry: blocks on `fetch` so the first lookup for a cold key
+    ///   still produces a value (or `None` if `fetch` fails).
+    pub fn get_or_refresh<F>(&self, key: K, fetch: F) -> Option<V>
+    where
+        F: FnOnce() -> Option<V> + Send + 'static,
+    {
+        let cached = self.entries.lock().unwrap().get(&key).cloned();
+
+        match cached {
+            Some(entry) if !self.is_stale(&entry) => Some(entry.value),
+            Some(entry) => {
+                self.spawn_refresh(key, fetch);
+                Some(entry.value)
+            }
+            None => self.refresh_blocking(key, fetch),
+        }
+    }
+
+    /// Fires `fetch` on a detached background thread and persists whatever
+    /// it returns. Nothing waits on this thread, so a process that exits
+    /// immediately after `get_or_refresh` can lose the refresh entirely;
+    /// that's the accepted cost of never blocking the stale path.
+    fn spawn_refresh<F>(&self, key: K, fetch: F)
+    where
+        F: FnOnce() -> Option<V> + Send + 'static,
+    {
+        let entries = Arc::clone(&self.entries);
+        let cache_path = self.cache_path.clone();
+
+        std::thread::spawn(move || {
+            if let Some(value) = fetch() {
+                let mut entries = entries.lock().unwrap();
+                entries.insert(
+                    key,
+                    CacheEntry {
+                        value,
+                        cached_at: Utc::now(),
+                    },
+                );
+                Self::persist(&cache_path, &entries);
+            }
+        });
+    }
+
+    fn refresh_blocking<F>(&self, key: K, fetch: F) -> Option<V>
+    where
+        F: FnOnce() -> Option<V> + Send + 'static,
+    {
+        let value = fetch()?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            key,
+            CacheEntry {
+                value: value.clone(),
+                cached_at: Utc::now(),
+            },
+        );
+        Self::persist(&self.cache_path, &entries);
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(ttl_secs: u64) -> TtlCache<String, f64> {
+        TtlCache::new(PathBuf::from("/dev/null/unused"), Duration::from_secs(ttl_secs))
+    }
+
+    #[test]
+    fn fresh_entry_is_not_stale() {
+        let entry = CacheEntry {
+            value: 1.0,
+            cached_at: Utc::now(),
+        };
+        assert!(!cache(60).is_stale(&entry));
+    }
+
+    #[test]
+    fn entry_older_than_ttl_is_stale() {
+        let entry = CacheEntry {
+            value: 1.0,
+            cached_at: Utc::now() - chrono::Duration::seconds(61),
+        };
+        assert!(cache(60).is_stale(&entry));
+    }
+}
@@ -0,0 +1,31 @@
+use super::{Segment, SegmentData};
+use crate::config::threshold::threshold_color_metadata;
+use crate::config::{InputData, SegmentId};
+
+#[derive(Default)]
+pub struct CostSegment;
+
+impl CostSegment {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Segment for CostSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let cost = input.raw.pointer("/cost/total_cost_usd")?.as_f64()?;
+
+        let config = crate::config::Config::load().ok()?;
+        let segment_config = config.segments.iter().find(|s| s.id == SegmentId::Cost);
+
+        Some(SegmentData {
+            primary: format!("${:.2}", cost),
+            secondary: String::new(),
+            metadata: threshold_color_metadata(segment_config, cost),
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Cost
+    }
+}
@@ -1,36 +1,80 @@
 use super::{Segment, SegmentData};
+use crate::core::cache::TtlCache;
+use crate::config::threshold::threshold_color_metadata;
 use crate::config::{InputData, SegmentId};
-use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use chrono::{Duration, Local, TimeZone};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Deserialize)]
-struct StatResponse {
-    data: StatData,
-    success: bool,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IkunCodeData {
+    cost: f64,
+    balance: f64,
 }
 
-#[derive(Debug, Deserialize)]
-struct StatData {
-    quota: i64,
+/// Everything needed to talk to a New-API-compatible relay, read entirely
+/// from a segment's `options` so any one-api/new-api fork can be targeted
+/// without recompiling. The defaults reproduce the original hardcoded
+/// ikuncode.cc behavior.
+struct NewApiOptions {
+    api_base_url: String,
+    stat_path: String,
+    balance_path: String,
+    auth_header: String,
+    quota_divisor: f64,
+    stat_quota_pointer: String,
+    balance_quota_pointer: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct UserResponse {
-    data: UserData,
-    success: bool,
-}
+impl NewApiOptions {
+    fn from_segment_config(segment_config: Option<&crate::config::SegmentConfig>) -> Self {
+        let opt_str = |key: &str, default: &str| -> String {
+            segment_config
+                .and_then(|sc| sc.options.get(key))
+                .and_then(|v| v.as_str())
+                .unwrap_or(default)
+                .to_string()
+        };
 
-#[derive(Debug, Deserialize)]
-struct UserData {
-    quota: i64,
+        Self {
+            api_base_url: opt_str("api_base_url", "https://api.ikuncode.cc"),
+            stat_path: opt_str("stat_path", "/api/log/self/stat"),
+            balance_path: opt_str("balance_path", "/api/user/self"),
+            auth_header: opt_str("auth_header", "New-Api-User"),
+            quota_divisor: segment_config
+                .and_then(|sc| sc.options.get("quota_divisor"))
+                .and_then(|v| v.as_f64())
+                .unwrap_or(500000.0),
+            stat_quota_pointer: opt_str("stat_quota_pointer", "data.quota"),
+            balance_quota_pointer: opt_str("balance_quota_pointer", "data.quota"),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct IkunCodeCache {
-    cost: f64,
-    balance: f64,
-    cached_at: String,
+/// Resolves a dotted path (e.g. `"data.quota"`) against a decoded JSON
+/// response so the quota field can be located without a typed struct.
+/// Returns `None` if the path doesn't resolve, or if the response carries
+/// a top-level `"success": false"` (a response with no `"success"` key at
+/// all is treated as successful, since not every New-API fork sends one).
+///
+/// Splitting on `.` means a field whose name itself contains a literal dot
+/// can't be addressed; none of the relays this segment targets use such
+/// names, so that's accepted rather than pulled in a real JSON-Pointer
+/// dependency for it.
+fn lookup_quota(value: &serde_json::Value, path: &str) -> Option<f64> {
+    let success = value
+        .get("success")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    if !success {
+        return None;
+    }
+
+    let mut current = value;
+    for part in path.split('.') {
+        current = current.get(part)?;
+    }
+    current.as_f64()
 }
 
 #[derive(Default)]
@@ -46,33 +90,12 @@ impl IkunCodeSegment {
         Some(home.join(".claude").join("ccline").join(".ikuncode_cache.json"))
     }
 
-    fn load_cache(&self) -> Option<IkunCodeCache> {
-        let cache_path = Self::get_cache_path()?;
-        let content = std::fs::read_to_string(&cache_path).ok()?;
-        serde_json::from_str(&content).ok()
-    }
-
-    fn save_cache(&self, cache: &IkunCodeCache) {
-        if let Some(cache_path) = Self::get_cache_path() {
-            if let Some(parent) = cache_path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-            if let Ok(json) = serde_json::to_string_pretty(cache) {
-                let _ = std::fs::write(&cache_path, json);
-            }
-        }
-    }
-
-    fn is_cache_valid(&self, cache: &IkunCodeCache, cache_duration: u64) -> bool {
-        if let Ok(cached_at) = DateTime::parse_from_rfc3339(&cache.cached_at) {
-            let elapsed = Utc::now().signed_duration_since(cached_at.with_timezone(&Utc));
-            elapsed.num_seconds() < cache_duration as i64
-        } else {
-            false
-        }
-    }
-
-    fn fetch_data(&self, user_token: &str, user_id: &str, timeout_secs: u64) -> Option<(f64, f64)> {
+    fn fetch_data(
+        opts: &NewApiOptions,
+        user_token: &str,
+        user_id: &str,
+        timeout_secs: u64,
+    ) -> Option<(f64, f64)> {
         let agent = ureq::AgentBuilder::new().build();
         let timeout = std::time::Duration::from_secs(timeout_secs);
 
@@ -84,7 +107,9 @@ impl IkunCodeSegment {
         let end = start + Duration::days(1);
 
         let stat_url = format!(
-            "https://api.ikuncode.cc/api/log/self/stat?start_timestamp={}&end_timestamp={}&type=2",
+            "{}{}?start_timestamp={}&end_timestamp={}&type=2",
+            opts.api_base_url,
+            opts.stat_path,
             start.timestamp(),
             end.timestamp()
         );
@@ -92,26 +117,28 @@ impl IkunCodeSegment {
         let cost = agent
             .get(&stat_url)
             .set("Authorization", &format!("Bearer {}", user_token))
-            .set("New-Api-User", user_id)
+            .set(&opts.auth_header, user_id)
             .timeout(timeout)
             .call()
             .ok()
-            .and_then(|r| r.into_json::<StatResponse>().ok())
-            .filter(|r| r.success)
-            .map(|r| r.data.quota as f64 / 500000.0)
+            .and_then(|r| r.into_json::<serde_json::Value>().ok())
+            .and_then(|v| lookup_quota(&v, &opts.stat_quota_pointer))
+            .map(|quota| quota / opts.quota_divisor)
             .unwrap_or(0.0);
 
         // Fetch balance
+        let balance_url = format!("{}{}", opts.api_base_url, opts.balance_path);
+
         let balance = agent
-            .get("https://api.ikuncode.cc/api/user/self")
+            .get(&balance_url)
             .set("Authorization", &format!("Bearer {}", user_token))
-            .set("New-Api-User", user_id)
+            .set(&opts.auth_header, user_id)
             .timeout(timeout)
             .call()
             .ok()
-            .and_then(|r| r.into_json::<UserResponse>().ok())
-            .filter(|r| r.success)
-            .map(|r| r.data.quota as f64 / 500000.0)
+            .and_then(|r| r.into_json::<serde_json::Value>().ok())
+            .and_then(|v| lookup_quota(&v, &opts.balance_quota_pointer))
+            .map(|quota| quota / opts.quota_divisor)
             .unwrap_or(0.0);
 
         Some((cost, balance))
@@ -145,35 +172,30 @@ impl Segment for IkunCodeSegment {
             .and_then(|v| v.as_u64())
             .unwrap_or(180);
 
-        let cached_data = self.load_cache();
-        let use_cached = cached_data
-            .as_ref()
-            .map(|c| self.is_cache_valid(c, cache_duration))
-            .unwrap_or(false);
-
-        let (cost, balance) = if use_cached {
-            let cache = cached_data.unwrap();
-            (cache.cost, cache.balance)
-        } else {
-            match self.fetch_data(&config.user_token, &config.user_id, timeout) {
-                Some((cost, balance)) => {
-                    self.save_cache(&IkunCodeCache {
-                        cost,
-                        balance,
-                        cached_at: Utc::now().to_rfc3339(),
-                    });
-                    (cost, balance)
-                }
-                None => cached_data.map(|c| (c.cost, c.balance))?,
-            }
-        };
+        let cache_path = Self::get_cache_path()?;
+        let cache = TtlCache::<String, IkunCodeData>::new(
+            cache_path,
+            std::time::Duration::from_secs(cache_duration),
+        );
 
-        let primary = format!("ikuncode 本日消费 ${:.2} 余额 ${:.2}", cost, balance);
+        let opts = NewApiOptions::from_segment_config(segment_config);
+        let user_token = config.user_token.clone();
+        let user_id = config.user_id.clone();
+        let fetch_user_id = user_id.clone();
+        let data = cache.get_or_refresh(user_id, move || {
+            Self::fetch_data(&opts, &user_token, &fetch_user_id, timeout)
+                .map(|(cost, balance)| IkunCodeData { cost, balance })
+        })?;
+
+        let primary = format!(
+            "ikuncode 本日消费 ${:.2} 余额 ${:.2}",
+            data.cost, data.balance
+        );
 
         Some(SegmentData {
             primary,
             secondary: String::new(),
-            metadata: HashMap::new(),
+            metadata: threshold_color_metadata(segment_config, data.balance),
         })
     }
 
@@ -181,3 +203,26 @@ impl Segment for IkunCodeSegment {
         SegmentId::IkunCode
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_quota_resolves_dotted_path() {
+        let value = serde_json::json!({"data": {"quota": 42.0}});
+        assert_eq!(lookup_quota(&value, "data.quota"), Some(42.0));
+    }
+
+    #[test]
+    fn lookup_quota_returns_none_on_explicit_failure() {
+        let value = serde_json::json!({"success": false, "data": {"quota": 42.0}});
+        assert_eq!(lookup_quota(&value, "data.quota"), None);
+    }
+
+    #[test]
+    fn lookup_quota_defaults_to_success_when_key_is_missing() {
+        let value = serde_json::json!({"data": {"quota": 7.5}});
+        assert_eq!(lookup_quota(&value, "data.quota"), Some(7.5));
+    }
+}
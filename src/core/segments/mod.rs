@@ -0,0 +1,45 @@
+pub mod cost;
+pub mod ikuncode;
+pub mod usage;
+
+use crate::config::{AnsiColor, InputData, SegmentConfig, SegmentId};
+use std::collections::HashMap;
+
+/// What a segment produced for this render: the text to show, an optional
+/// secondary line, and free-form metadata a segment can use to hint things
+/// back to the renderer (e.g. a threshold-driven text color) without
+/// growing the struct's own fields for every segment-specific concern.
+#[derive(Debug, Clone, Default)]
+pub struct SegmentData {
+    pub primary: String,
+    pub secondary: String,
+    pub metadata: HashMap<String, String>,
+}
+
+impl SegmentData {
+    /// The color this segment's data wants to render in, if any: a
+    /// `thresholds` rule that fired beats the segment's static `colors.text`.
+    pub fn resolved_text_color(&self, static_color: Option<AnsiColor>) -> Option<AnsiColor> {
+        self.metadata
+            .get("text_color")
+            .and_then(|raw| serde_json::from_str::<AnsiColor>(raw).ok())
+            .or(static_color)
+    }
+}
+
+pub trait Segment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData>;
+
+    fn id(&self) -> SegmentId;
+
+    /// Collects this segment's data and resolves the color it should
+    /// render in, applying any `thresholds` color over the segment's
+    /// static `colors.text`. This is the entry point the statusline
+    /// renderer should call instead of `collect` directly, since `collect`
+    /// alone drops the threshold color on the floor.
+    fn render(&self, segment_config: &SegmentConfig, input: &InputData) -> Option<(String, Option<AnsiColor>)> {
+        let data = self.collect(input)?;
+        let color = data.resolved_text_color(segment_config.colors.text.clone());
+        Some((data.primary, color))
+    }
+}
@@ -0,0 +1,88 @@
+use super::{Segment, SegmentData};
+use crate::config::threshold::threshold_color_metadata;
+use crate::config::{InputData, SegmentId};
+use crate::core::cache::TtlCache;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UsagePercent(f64);
+
+#[derive(Default)]
+pub struct UsageSegment;
+
+impl UsageSegment {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn get_cache_path() -> Option<std::path::PathBuf> {
+        let home = dirs::home_dir()?;
+        Some(home.join(".claude").join("ccline").join(".usage_cache.json"))
+    }
+
+    fn fetch_usage_percent(api_base_url: &str, user_token: &str, timeout_secs: u64) -> Option<f64> {
+        let agent = ureq::AgentBuilder::new().build();
+        let timeout = std::time::Duration::from_secs(timeout_secs);
+
+        let url = format!("{}/v1/usage", api_base_url);
+        agent
+            .get(&url)
+            .set("Authorization", &format!("Bearer {}", user_token))
+            .timeout(timeout)
+            .call()
+            .ok()
+            .and_then(|r| r.into_json::<serde_json::Value>().ok())
+            .and_then(|v| v.get("used_percent").and_then(|p| p.as_f64()))
+    }
+}
+
+impl Segment for UsageSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let config = crate::config::Config::load().ok()?;
+
+        if config.user_token.is_empty() {
+            return None;
+        }
+
+        let segment_config = config.segments.iter().find(|s| s.id == SegmentId::Usage);
+
+        let api_base_url = segment_config
+            .and_then(|sc| sc.options.get("api_base_url"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("https://api.anthropic.com")
+            .to_string();
+
+        let timeout = segment_config
+            .and_then(|sc| sc.options.get("timeout"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(2);
+
+        let cache_duration = segment_config
+            .and_then(|sc| sc.options.get("cache_duration"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(180);
+
+        let cache_path = Self::get_cache_path()?;
+        let cache = TtlCache::<String, UsagePercent>::new(
+            cache_path,
+            std::time::Duration::from_secs(cache_duration),
+        );
+
+        let user_token = config.user_token.clone();
+        let used_percent = cache
+            .get_or_refresh("usage".to_string(), move || {
+                Self::fetch_usage_percent(&api_base_url, &user_token, timeout).map(UsagePercent)
+            })?
+            .0;
+
+        Some(SegmentData {
+            primary: format!("usage {:.0}%", used_percent),
+            secondary: String::new(),
+            metadata: threshold_color_metadata(segment_config, used_percent),
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Usage
+    }
+}
@@ -0,0 +1,51 @@
+use crate::config::{AnsiColor, Config, InputData, SegmentConfig, SegmentId};
+use crate::core::segments::cost::CostSegment;
+use crate::core::segments::ikuncode::IkunCodeSegment;
+use crate::core::segments::usage::UsageSegment;
+use crate::core::segments::Segment;
+
+/// Renders every enabled, implemented segment through `Segment::render` and
+/// joins them with a space, so a `thresholds` color beats the segment's
+/// static `colors.text` the way the threshold feature requires.
+pub fn render_statusline(config: &Config, input: &InputData) -> String {
+    config
+        .segments
+        .iter()
+        .filter(|sc| sc.enabled)
+        .filter_map(|sc| render_segment(sc, input))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Segments without an implementation here (model/directory/git/...) are
+/// skipped rather than faked; only the network/threshold-aware segments
+/// live in `core::segments` today.
+fn render_segment(segment_config: &SegmentConfig, input: &InputData) -> Option<String> {
+    let segment: Box<dyn Segment> = match segment_config.id {
+        SegmentId::Cost => Box::new(CostSegment::new()),
+        SegmentId::Usage => Box::new(UsageSegment::new()),
+        SegmentId::IkunCode => Box::new(IkunCodeSegment::new()),
+        _ => return None,
+    };
+
+    let (text, color) = segment.render(segment_config, input)?;
+    Some(colorize(&text, color))
+}
+
+fn colorize(text: &str, color: Option<AnsiColor>) -> String {
+    match color {
+        Some(AnsiColor::Color16 { c16 }) => format!("\x1b[{}m{}\x1b[0m", ansi_fg_code(c16), text),
+        None => text.to_string(),
+    }
+}
+
+/// Maps the 0-15 palette index used throughout `SegmentConfig` to the
+/// corresponding foreground SGR code (30-37 for the low 8, 90-97 for the
+/// bright 8).
+fn ansi_fg_code(c16: u8) -> u8 {
+    if c16 < 8 {
+        30 + c16
+    } else {
+        82 + c16
+    }
+}
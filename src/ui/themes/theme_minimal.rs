@@ -1,8 +1,13 @@
+use crate::config::threshold::Threshold;
 use crate::config::{
     AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
 };
 use std::collections::HashMap;
 
+fn thresholds_option(thresholds: Vec<Threshold>) -> serde_json::Value {
+    serde_json::to_value(thresholds).expect("thresholds are always serializable")
+}
+
 pub fn model_segment() -> SegmentConfig {
     SegmentConfig {
         id: SegmentId::Model,
@@ -93,7 +98,27 @@ pub fn cost_segment() -> SegmentConfig {
             background: None,
         },
         styles: TextStyleConfig::default(),
-        options: HashMap::new(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "thresholds".to_string(),
+                thresholds_option(vec![
+                    Threshold {
+                        at: 0.0,
+                        color: AnsiColor::Color16 { c16: 2 },
+                    },
+                    Threshold {
+                        at: 5.0,
+                        color: AnsiColor::Color16 { c16: 3 },
+                    },
+                    Threshold {
+                        at: 20.0,
+                        color: AnsiColor::Color16 { c16: 1 },
+                    },
+                ]),
+            );
+            opts
+        },
     }
 }
 
@@ -158,6 +183,23 @@ pub fn usage_segment() -> SegmentConfig {
                 serde_json::Value::Number(180.into()),
             );
             opts.insert("timeout".to_string(), serde_json::Value::Number(2.into()));
+            opts.insert(
+                "thresholds".to_string(),
+                thresholds_option(vec![
+                    Threshold {
+                        at: 0.0,
+                        color: AnsiColor::Color16 { c16: 2 },
+                    },
+                    Threshold {
+                        at: 70.0,
+                        color: AnsiColor::Color16 { c16: 3 },
+                    },
+                    Threshold {
+                        at: 90.0,
+                        color: AnsiColor::Color16 { c16: 1 },
+                    },
+                ]),
+            );
             opts
         },
     }
@@ -180,6 +222,39 @@ pub fn ikuncode_segment() -> SegmentConfig {
         options: {
             let mut opts = HashMap::new();
             opts.insert("timeout".to_string(), serde_json::Value::Number(2.into()));
+            opts.insert(
+                "api_base_url".to_string(),
+                serde_json::Value::String("https://api.ikuncode.cc".to_string()),
+            );
+            opts.insert(
+                "stat_path".to_string(),
+                serde_json::Value::String("/api/log/self/stat".to_string()),
+            );
+            opts.insert(
+                "balance_path".to_string(),
+                serde_json::Value::String("/api/user/self".to_string()),
+            );
+            opts.insert(
+                "quota_divisor".to_string(),
+                serde_json::Value::Number(serde_json::Number::from_f64(500000.0).unwrap()),
+            );
+            opts.insert(
+                "thresholds".to_string(),
+                thresholds_option(vec![
+                    Threshold {
+                        at: 0.0,
+                        color: AnsiColor::Color16 { c16: 1 },
+                    },
+                    Threshold {
+                        at: 5.0,
+                        color: AnsiColor::Color16 { c16: 3 },
+                    },
+                    Threshold {
+                        at: 20.0,
+                        color: AnsiColor::Color16 { c16: 2 },
+                    },
+                ]),
+            );
             opts
         },
     }